@@ -1,11 +1,63 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::{Arc, Mutex, RwLock},
+};
 
-use axum::Router;
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
 use log::{info, warn};
+use serde::Serialize;
+
+/// Stability level of a `SiteFeature`, mirroring rustc tidy's feature
+/// `Status` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureStatus {
+    Stable,
+    Experimental,
+    Deprecated,
+    Removed,
+}
 
 pub enum FeatureError<'a> {
     Failure(&'a str),
-    DoesNotExist
+    DoesNotExist,
+    /// Returned when disabling a feature that another currently-enabled
+    /// feature still lists as a dependency.
+    DependencyConflict(String),
+    /// A registration-time problem found by `SiteFeatureBuilder::validate`.
+    Invalid(String),
+}
+
+impl<'a> std::fmt::Display for FeatureError<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeatureError::Failure(msg) => write!(f, "{msg}"),
+            FeatureError::DoesNotExist => write!(f, "feature does not exist"),
+            FeatureError::DependencyConflict(dependent) => {
+                write!(f, "feature is still required by '{dependent}'")
+            }
+            FeatureError::Invalid(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+/// JSON projection of a registered feature, served by the `/_features`
+/// introspection router.
+#[derive(Serialize)]
+pub struct FeatureManifestEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub subpath: String,
+    pub enabled: bool,
 }
 
 pub trait SiteFeatureStorage {
@@ -13,7 +65,7 @@ pub trait SiteFeatureStorage {
     fn set_enabled(&mut self, id: &str, enabled: bool) -> Result<(), FeatureError>; 
 }
 
-pub trait SiteFeature {
+pub trait SiteFeature: Send {
     fn get_router(&self) -> Router;
     fn setup(&mut self) -> Result<(), FeatureError>;
     fn shutdown(&mut self) -> Result<(), FeatureError> {
@@ -21,6 +73,14 @@ pub trait SiteFeature {
     }
 
     fn get_id(&self) -> &str;
+    /// Ids of features that must be enabled before this one. Resolved by
+    /// `SiteFeatureSystem::set_enabled` into an activation order.
+    fn get_dependencies(&self) -> &[&str] {
+        &[]
+    }
+    fn get_status(&self) -> FeatureStatus {
+        FeatureStatus::Stable
+    }
     fn get_subpath(&self) -> &str {
         "/"
     }
@@ -34,7 +94,13 @@ pub trait SiteFeature {
 
 pub struct SiteFeatureSystem<T: SiteFeatureStorage> {
     storage: T,
-    features: HashMap<String, Box<dyn SiteFeature>>
+    features: HashMap<String, Box<dyn SiteFeature>>,
+    allow_experimental: bool,
+    /// Live mirror of which feature ids are enabled, consulted by the
+    /// routing middleware on every request so toggling a feature through
+    /// `set_enabled` takes effect immediately, without rebuilding the
+    /// `Router`.
+    enabled: Arc<RwLock<HashSet<String>>>,
 }
 
 impl<T: SiteFeatureStorage> SiteFeatureSystem<T> {
@@ -46,17 +112,77 @@ impl<T: SiteFeatureStorage> SiteFeatureSystem<T> {
         vec
     }
 
+    /// Builds the combined `Router` once. Each feature's sub-router is
+    /// nested behind a gate that consults the shared `enabled` set on every
+    /// request, so a later `set_enabled` call changes live routing without
+    /// this `Router` ever being rebuilt.
     fn get_router(&self) -> Router {
         let mut router = Router::new();
 
-        for feature in &self.features {
-            let feature_router = feature.1.get_router();
-            let path = feature.1.get_subpath();
-            router = router.nest(path, feature_router);
+        for (id, feature) in &self.features {
+            let feature_router = feature.get_router().layer(middleware::from_fn({
+                let enabled = self.enabled.clone();
+                let id = id.clone();
+                move |req, next| gate_feature(enabled.clone(), id.clone(), req, next)
+            }));
+            router = router.nest(feature.get_subpath(), feature_router);
         }
 
         router
     }
+
+    /// Walks the dependency graph of `id` and returns every transitive
+    /// prerequisite in the order it must be set up before `id` itself, `id`
+    /// included as the last entry. Already-enabled prerequisites are listed
+    /// too; `set_enabled` skips them. Errors with `DoesNotExist` on an unknown
+    /// id, or `Failure` if the graph contains a cycle.
+    pub fn resolve_order(&self, id: &str) -> Result<Vec<String>, FeatureError> {
+        let mut order = Vec::new();
+        let mut visiting = HashSet::new();
+        let mut visited = HashSet::new();
+        self.visit_dependencies(id, &mut visiting, &mut visited, &mut order)?;
+        Ok(order)
+    }
+
+    fn visit_dependencies<'a>(
+        &self,
+        id: &str,
+        visiting: &mut HashSet<String>,
+        visited: &mut HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), FeatureError<'a>> {
+        if visited.contains(id) {
+            return Ok(());
+        }
+        if !visiting.insert(id.to_string()) {
+            return Err(FeatureError::Failure("cycle detected in feature dependency graph"));
+        }
+
+        let feature = self.features.get(id).ok_or(FeatureError::DoesNotExist)?;
+        for dep in feature.get_dependencies() {
+            self.visit_dependencies(dep, visiting, visited, order)?;
+        }
+
+        visiting.remove(id);
+        visited.insert(id.to_string());
+        order.push(id.to_string());
+        Ok(())
+    }
+
+    /// Undoes an in-progress enable: shuts down and unmarks every id in
+    /// `committed`, most-recently-enabled first, so a failed `set_enabled`
+    /// leaves state exactly as it found it.
+    fn rollback_enable(&mut self, committed: Vec<String>) {
+        for step_id in committed.into_iter().rev() {
+            if let Some(feature) = self.features.get_mut(&step_id) {
+                let _ = feature.shutdown();
+            }
+            if let Err(err) = self.storage.set_enabled(&step_id, false) {
+                warn!("failed to roll back storage for '{step_id}' during enable rollback: {err}");
+            }
+            self.enabled.write().unwrap().remove(&step_id);
+        }
+    }
 }
 
 impl<T: SiteFeatureStorage> SiteFeatureStorage for SiteFeatureSystem<T> {
@@ -65,42 +191,200 @@ impl<T: SiteFeatureStorage> SiteFeatureStorage for SiteFeatureSystem<T> {
     }
     fn set_enabled(&mut self, id: &str, enabled: bool) -> Result<(), FeatureError> {
         let prev_enabled = self.storage.get_enabled(id);
-        
+
         if prev_enabled == enabled {
             return Ok(());
         }
 
-        let feature = self.features.get_mut(id);
-        match feature {
-            Some(v) => match prev_enabled {
-                true => {
-                    v.shutdown()?;
+        if enabled {
+            let order = self.resolve_order(id)?;
+
+            // Check every node's stability gate before touching any state,
+            // so a stable feature that transitively depends on a gated
+            // Experimental one is rejected without first enabling the
+            // prerequisites ordered ahead of it.
+            for step_id in &order {
+                if self.storage.get_enabled(step_id) {
+                    continue;
+                }
+
+                let feature = self.features.get(step_id).ok_or(FeatureError::DoesNotExist)?;
+                if feature.get_status() == FeatureStatus::Experimental && !self.allow_experimental {
+                    return Err(FeatureError::Failure("feature is Experimental and experimental features are not allowed"));
                 }
-                false => {
-                    v.setup()?;
+            }
+
+            // Bring prerequisites up one at a time, but track what we've
+            // already committed so a later setup() failure can be rolled
+            // back instead of leaving earlier prerequisites enabled while
+            // this call returns an error.
+            let mut committed = Vec::new();
+            for step_id in order {
+                if self.storage.get_enabled(&step_id) {
+                    continue;
+                }
+
+                let feature = self.features.get_mut(&step_id).ok_or(FeatureError::DoesNotExist)?;
+                if let Err(err) = feature.setup() {
+                    self.rollback_enable(committed);
+                    return Err(err);
+                }
+
+                if let Err(err) = self.storage.set_enabled(&step_id, true) {
+                    // setup() already ran for step_id; shut it back down
+                    // too so a storage failure doesn't leave it running
+                    // without being recorded as enabled anywhere.
+                    if let Some(feature) = self.features.get_mut(&step_id) {
+                        let _ = feature.shutdown();
+                    }
+                    self.rollback_enable(committed);
+                    return Err(err);
                 }
+                self.enabled.write().unwrap().insert(step_id.clone());
+                committed.push(step_id);
             }
-            None => {
-                return Err(FeatureError::DoesNotExist);
+        } else {
+            for (other_id, other) in &self.features {
+                if other_id == id {
+                    continue;
+                }
+                if self.storage.get_enabled(other_id) && other.get_dependencies().contains(&id) {
+                    return Err(FeatureError::DependencyConflict(other_id.clone()));
+                }
             }
+
+            let feature = self.features.get_mut(id).ok_or(FeatureError::DoesNotExist)?;
+            feature.shutdown()?;
+            self.storage.set_enabled(id, false)?;
+            self.enabled.write().unwrap().remove(id);
         }
 
-        self.storage.set_enabled(id, enabled);
         Ok(())
     }
 }
 
+impl<T: SiteFeatureStorage + Send + 'static> SiteFeatureSystem<T> {
+    fn get_manifest(&self) -> Vec<FeatureManifestEntry> {
+        self.features
+            .iter()
+            .map(|(id, feature)| FeatureManifestEntry {
+                id: id.clone(),
+                name: feature.get_name().to_string(),
+                description: feature.get_description().to_string(),
+                subpath: feature.get_subpath().to_string(),
+                enabled: self.storage.get_enabled(id),
+            })
+            .collect()
+    }
+
+    /// Mounts a read-only introspection router under `/_features` backed by
+    /// `system`, plus per-feature enable/disable endpoints that call through
+    /// to `set_enabled`. Lets an admin panel list and toggle features over
+    /// JSON instead of reading an autogenerated summary.
+    pub fn get_manifest_router(system: Arc<Mutex<Self>>) -> Router {
+        Router::new()
+            .route("/_features", get(list_features::<T>))
+            .route("/_features/:id", get(get_feature::<T>))
+            .route("/_features/:id/enable", post(enable_feature::<T>))
+            .route("/_features/:id/disable", post(disable_feature::<T>))
+            .with_state(system)
+    }
+}
+
+async fn list_features<T: SiteFeatureStorage + Send + 'static>(
+    State(system): State<Arc<Mutex<SiteFeatureSystem<T>>>>,
+) -> Json<Vec<FeatureManifestEntry>> {
+    let system = system.lock().unwrap();
+    Json(system.get_manifest())
+}
+
+async fn get_feature<T: SiteFeatureStorage + Send + 'static>(
+    State(system): State<Arc<Mutex<SiteFeatureSystem<T>>>>,
+    Path(id): Path<String>,
+) -> Result<Json<FeatureManifestEntry>, StatusCode> {
+    let system = system.lock().unwrap();
+    system
+        .get_manifest()
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn enable_feature<T: SiteFeatureStorage + Send + 'static>(
+    State(system): State<Arc<Mutex<SiteFeatureSystem<T>>>>,
+    Path(id): Path<String>,
+) -> StatusCode {
+    set_feature_enabled(system, &id, true)
+}
+
+async fn disable_feature<T: SiteFeatureStorage + Send + 'static>(
+    State(system): State<Arc<Mutex<SiteFeatureSystem<T>>>>,
+    Path(id): Path<String>,
+) -> StatusCode {
+    set_feature_enabled(system, &id, false)
+}
+
+fn set_feature_enabled<T: SiteFeatureStorage + Send + 'static>(
+    system: Arc<Mutex<SiteFeatureSystem<T>>>,
+    id: &str,
+    enabled: bool,
+) -> StatusCode {
+    let mut system = system.lock().unwrap();
+    match system.set_enabled(id, enabled) {
+        Ok(()) => StatusCode::OK,
+        Err(FeatureError::DoesNotExist) => StatusCode::NOT_FOUND,
+        Err(FeatureError::Failure(_)) | Err(FeatureError::DependencyConflict(_)) => StatusCode::CONFLICT,
+        Err(FeatureError::Invalid(_)) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+async fn gate_feature(
+    enabled: Arc<RwLock<HashSet<String>>>,
+    id: String,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if enabled.read().unwrap().contains(&id) {
+        next.run(req).await
+    } else {
+        StatusCode::NOT_FOUND.into_response()
+    }
+}
+
+fn is_valid_slug(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('-')
+        && !id.ends_with('-')
+        && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
 pub struct SiteFeatureBuilder {
-    features: HashMap<String, Box<dyn SiteFeature>>
+    features: HashMap<String, Box<dyn SiteFeature>>,
+    allow_experimental: bool,
+    /// Ids that were registered more than once, the earlier registration
+    /// having been silently dropped by `add_feature`. Surfaced as hard
+    /// errors by `validate`.
+    collisions: HashSet<String>,
 }
 
 impl SiteFeatureBuilder {
     fn new() -> SiteFeatureBuilder {
         SiteFeatureBuilder {
-            features: HashMap::new()
+            features: HashMap::new(),
+            allow_experimental: false,
+            collisions: HashSet::new(),
         }
     }
 
+    /// Allows `Experimental` features to be enabled at runtime. Off by
+    /// default, keeping unfinished features out of production routing even
+    /// though they're compiled in.
+    fn allow_experimental(mut self, allow: bool) -> Self {
+        self.allow_experimental = allow;
+        self
+    }
+
     fn add_feature<F: SiteFeature + 'static>(mut self, feature: F) -> Self {
         let id = feature.get_id().to_string();
         let name: String = feature.get_name().to_string();
@@ -114,6 +398,7 @@ impl SiteFeatureBuilder {
             Some(v) => {
                 let prev_name = v.get_name();
                 warn!("Feature {name} (id of \'{id}\') overrides {prev_name}");
+                self.collisions.insert(id);
             }
             None => {}
         }
@@ -121,10 +406,301 @@ impl SiteFeatureBuilder {
         self
     }
 
-    fn build<T: SiteFeatureStorage>(self, storage: T) -> SiteFeatureSystem::<T> {
+    /// Runs rustc tidy-style checks over the registered features before
+    /// `build`: no two features may claim the same `get_subpath()` (which
+    /// would make `Router::nest` collide or silently shadow), every
+    /// `get_id()` must be a non-empty slug, every subpath must be a valid
+    /// nest prefix starting with `/`, and no id may have been silently
+    /// overridden by a later registration.
+    pub fn validate(&self) -> Result<(), Vec<FeatureError<'static>>> {
+        let mut errors = Vec::new();
+
+        for id in &self.collisions {
+            errors.push(FeatureError::Invalid(format!(
+                "feature id '{id}' was registered more than once; the earlier feature was silently dropped"
+            )));
+        }
+
+        let mut subpaths: HashMap<&str, &str> = HashMap::new();
+        for (id, feature) in &self.features {
+            if !is_valid_slug(id) {
+                errors.push(FeatureError::Invalid(format!(
+                    "feature id '{id}' is not a valid slug (lowercase alphanumeric and hyphens only)"
+                )));
+            }
+
+            let subpath = feature.get_subpath();
+            if !subpath.starts_with('/') {
+                errors.push(FeatureError::Invalid(format!(
+                    "feature '{id}' has subpath '{subpath}', which is not a valid Router::nest prefix (must start with '/')"
+                )));
+                continue;
+            }
+
+            if let Some(other_id) = subpaths.insert(subpath, id) {
+                errors.push(FeatureError::Invalid(format!(
+                    "features '{other_id}' and '{id}' both claim subpath '{subpath}'"
+                )));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Runs `validate` and only then builds, propagating every accumulated
+    /// problem instead of registering a broken feature set.
+    pub fn try_build<T: SiteFeatureStorage>(
+        self,
+        storage: T,
+    ) -> Result<SiteFeatureSystem<T>, Vec<FeatureError<'static>>> {
+        self.validate()?;
+        Ok(self.build(storage))
+    }
+
+    fn build<T: SiteFeatureStorage>(mut self, mut storage: T) -> SiteFeatureSystem::<T> {
+        self.features.retain(|id, feature| {
+            let name = feature.get_name();
+            match feature.get_status() {
+                FeatureStatus::Removed => {
+                    warn!("Feature {name} (id of '{id}') is marked Removed and will not be registered");
+                    false
+                }
+                FeatureStatus::Deprecated => {
+                    warn!("Feature {name} (id of '{id}') is Deprecated");
+                    true
+                }
+                FeatureStatus::Experimental if !self.allow_experimental => {
+                    if let Err(err) = storage.set_enabled(id, false) {
+                        warn!("failed to force-disable Experimental feature '{id}' during build: {err}");
+                    }
+                    true
+                }
+                _ => true
+            }
+        });
+
+        let enabled = self
+            .features
+            .keys()
+            .filter(|id| storage.get_enabled(id))
+            .cloned()
+            .collect();
+
         SiteFeatureSystem {
             storage: storage,
-            features: self.features
+            features: self.features,
+            allow_experimental: self.allow_experimental,
+            enabled: Arc::new(RwLock::new(enabled)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::ServiceExt;
+
+    #[derive(Default)]
+    struct TestStorage {
+        enabled: HashMap<String, bool>,
+    }
+
+    impl SiteFeatureStorage for TestStorage {
+        fn get_enabled(&self, id: &str) -> bool {
+            *self.enabled.get(id).unwrap_or(&false)
+        }
+        fn set_enabled(&mut self, id: &str, enabled: bool) -> Result<(), FeatureError> {
+            self.enabled.insert(id.to_string(), enabled);
+            Ok(())
+        }
+    }
+
+    struct TestFeature {
+        id: &'static str,
+        subpath: &'static str,
+        dependencies: Vec<&'static str>,
+        status: FeatureStatus,
+        fail_setup: bool,
+    }
+
+    impl TestFeature {
+        fn new(id: &'static str) -> Self {
+            TestFeature {
+                id,
+                subpath: "/",
+                dependencies: Vec::new(),
+                status: FeatureStatus::Stable,
+                fail_setup: false,
+            }
+        }
+
+        fn depends_on(mut self, deps: &[&'static str]) -> Self {
+            self.dependencies = deps.to_vec();
+            self
+        }
+
+        fn with_status(mut self, status: FeatureStatus) -> Self {
+            self.status = status;
+            self
+        }
+
+        fn with_subpath(mut self, subpath: &'static str) -> Self {
+            self.subpath = subpath;
+            self
+        }
+
+        fn failing(mut self) -> Self {
+            self.fail_setup = true;
+            self
+        }
+    }
+
+    impl SiteFeature for TestFeature {
+        fn get_router(&self) -> Router {
+            Router::new().route("/", get(|| async { "ok" }))
+        }
+
+        fn setup(&mut self) -> Result<(), FeatureError> {
+            if self.fail_setup {
+                Err(FeatureError::Failure("boom"))
+            } else {
+                Ok(())
+            }
         }
+
+        fn get_id(&self) -> &str {
+            self.id
+        }
+
+        fn get_dependencies(&self) -> &[&str] {
+            &self.dependencies
+        }
+
+        fn get_status(&self) -> FeatureStatus {
+            self.status
+        }
+
+        fn get_subpath(&self) -> &str {
+            self.subpath
+        }
+    }
+
+    fn system_with(features: Vec<TestFeature>) -> SiteFeatureSystem<TestStorage> {
+        let mut builder = SiteFeatureBuilder::new();
+        for feature in features {
+            builder = builder.add_feature(feature);
+        }
+        builder.build(TestStorage::default())
+    }
+
+    #[test]
+    fn resolve_order_orders_dependencies_before_target() {
+        let system = system_with(vec![
+            TestFeature::new("base"),
+            TestFeature::new("mid").depends_on(&["base"]),
+            TestFeature::new("top").depends_on(&["mid"]),
+        ]);
+
+        let order = system.resolve_order("top").unwrap();
+        assert_eq!(order, vec!["base".to_string(), "mid".to_string(), "top".to_string()]);
+    }
+
+    #[test]
+    fn resolve_order_detects_cycle() {
+        let system = system_with(vec![
+            TestFeature::new("a").depends_on(&["b"]),
+            TestFeature::new("b").depends_on(&["a"]),
+        ]);
+
+        assert!(matches!(system.resolve_order("a"), Err(FeatureError::Failure(_))));
+    }
+
+    #[test]
+    fn enable_rolls_back_committed_prerequisites_on_setup_failure() {
+        let mut system = system_with(vec![
+            TestFeature::new("base"),
+            TestFeature::new("mid").depends_on(&["base"]).failing(),
+            TestFeature::new("top").depends_on(&["mid"]),
+        ]);
+
+        let result = system.set_enabled("top", true);
+
+        assert!(result.is_err());
+        assert!(!system.get_enabled("base"));
+        assert!(!system.get_enabled("mid"));
+        assert!(!system.get_enabled("top"));
+    }
+
+    #[test]
+    fn enable_rejects_transitively_experimental_dependency_without_mutating_state() {
+        let mut system = system_with(vec![
+            TestFeature::new("base"),
+            TestFeature::new("exp")
+                .depends_on(&["base"])
+                .with_status(FeatureStatus::Experimental),
+            TestFeature::new("top").depends_on(&["exp"]),
+        ]);
+
+        let result = system.set_enabled("top", true);
+
+        assert!(matches!(result, Err(FeatureError::Failure(_))));
+        assert!(!system.get_enabled("base"));
+        assert!(!system.get_enabled("exp"));
+    }
+
+    #[test]
+    fn enable_succeeds_for_experimental_dependency_when_allowed() {
+        let builder = SiteFeatureBuilder::new()
+            .allow_experimental(true)
+            .add_feature(TestFeature::new("base"))
+            .add_feature(
+                TestFeature::new("exp")
+                    .depends_on(&["base"])
+                    .with_status(FeatureStatus::Experimental),
+            )
+            .add_feature(TestFeature::new("top").depends_on(&["exp"]));
+        let mut system = builder.build(TestStorage::default());
+
+        system.set_enabled("top", true).unwrap();
+
+        assert!(system.get_enabled("base"));
+        assert!(system.get_enabled("exp"));
+        assert!(system.get_enabled("top"));
+    }
+
+    #[test]
+    fn validate_rejects_malformed_id_and_duplicate_subpath() {
+        let builder = SiteFeatureBuilder::new()
+            .add_feature(TestFeature::new("feature-a").with_subpath("/x"))
+            .add_feature(TestFeature::new("Bad ID!").with_subpath("/x"));
+
+        let errors = builder.validate().unwrap_err();
+
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, FeatureError::Invalid(msg) if msg.contains("slug"))));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, FeatureError::Invalid(msg) if msg.contains("both claim subpath"))));
+    }
+
+    #[tokio::test]
+    async fn disabled_feature_subpath_returns_404_until_enabled() {
+        let mut system = system_with(vec![TestFeature::new("widget").with_subpath("/widget")]);
+        let router = system.get_router();
+
+        let request = Request::builder().uri("/widget").body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        system.set_enabled("widget", true).unwrap();
+
+        let request = Request::builder().uri("/widget").body(Body::empty()).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
     }
 }
\ No newline at end of file